@@ -0,0 +1,198 @@
+// Compose-key mode: one activation hotkey (Ctrl+Alt+Space, registered by
+// `main`) lets the user type an accented character without a dedicated
+// hotkey per letter/diacritic combination. Activation installs a low-level
+// keyboard hook (`WH_KEYBOARD_LL`) that consumes the next two keystrokes:
+// a base letter (a/e/i/o/u/n/c), then a key that selects the diacritic
+// (grave, acute, circumflex, tilde, umlaut, cedilla). The diacritic keys
+// reuse the same physical key a US keyboard already uses for that glyph,
+// e.g. the backtick key is grave unshifted and tilde shifted.
+
+use std::sync::Mutex;
+
+use windows::Win32::Foundation::{HINSTANCE, LPARAM, LRESULT, WPARAM};
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::UI::Input::KeyboardAndMouse::GetAsyncKeyState;
+use windows::Win32::UI::WindowsAndMessaging::{
+    CallNextHookEx, HHOOK, KBDLLHOOKSTRUCT, SetWindowsHookExW, UnhookWindowsHookEx,
+    WH_KEYBOARD_LL, WM_KEYDOWN, WM_SYSKEYDOWN,
+};
+use windows::core::PCWSTR;
+
+use crate::send_unicode_str;
+
+const VK_SHIFT: i32 = 0x10;
+const VK_LSHIFT: u32 = 0xA0;
+const VK_RSHIFT: u32 = 0xA1;
+const VK_A: u32 = 0x41;
+const VK_C: u32 = 0x43;
+const VK_E: u32 = 0x45;
+const VK_I: u32 = 0x49;
+const VK_N: u32 = 0x4E;
+const VK_O: u32 = 0x4F;
+const VK_U: u32 = 0x55;
+const VK_6: u32 = 0x36;
+const VK_OEM_COMMA: u32 = 0xBC; // ,
+const VK_OEM_3: u32 = 0xC0; // ` / ~
+const VK_OEM_7: u32 = 0xDE; // ' / "
+
+enum Diacritic {
+    Grave,
+    Acute,
+    Circumflex,
+    Tilde,
+    Umlaut,
+    Cedilla,
+}
+
+enum State {
+    Idle,
+    AwaitingBase,
+    AwaitingDiacritic { base: char, uppercase: bool },
+}
+
+static STATE: Mutex<State> = Mutex::new(State::Idle);
+// Raw HHOOK value (HHOOK itself isn't Send/Sync); None when no hook is installed.
+static HOOK: Mutex<Option<isize>> = Mutex::new(None);
+
+/// Starts compose mode: installs the keyboard hook that will consume the
+/// next two keystrokes. A no-op if compose mode is already active.
+pub fn activate() {
+    let mut hook = HOOK.lock().unwrap();
+    if hook.is_some() {
+        return;
+    }
+
+    let hinstance = unsafe { GetModuleHandleW(PCWSTR(std::ptr::null())) }
+        .map(|h| HINSTANCE(h.0))
+        .unwrap_or_default();
+
+    if let Ok(h) = unsafe { SetWindowsHookExW(WH_KEYBOARD_LL, Some(hook_proc), hinstance, 0) } {
+        *hook = Some(h.0 as isize);
+        *STATE.lock().unwrap() = State::AwaitingBase;
+    }
+}
+
+fn deactivate() {
+    if let Some(handle) = HOOK.lock().unwrap().take() {
+        let _ = unsafe { UnhookWindowsHookEx(HHOOK(handle as *mut _)) };
+    }
+    *STATE.lock().unwrap() = State::Idle;
+}
+
+fn shift_held() -> bool {
+    (unsafe { GetAsyncKeyState(VK_SHIFT) } as u16 & 0x8000) != 0
+}
+
+fn base_from_vk(vk: u32) -> Option<char> {
+    Some(match vk {
+        VK_A => 'a',
+        VK_E => 'e',
+        VK_I => 'i',
+        VK_O => 'o',
+        VK_U => 'u',
+        VK_N => 'n',
+        VK_C => 'c',
+        _ => return None,
+    })
+}
+
+fn diacritic_from_vk(vk: u32, shift: bool) -> Option<Diacritic> {
+    Some(match (vk, shift) {
+        (VK_OEM_3, false) => Diacritic::Grave,
+        (VK_OEM_3, true) => Diacritic::Tilde,
+        (VK_OEM_7, false) => Diacritic::Acute,
+        (VK_OEM_7, true) => Diacritic::Umlaut,
+        (VK_6, true) => Diacritic::Circumflex,
+        (VK_OEM_COMMA, false) => Diacritic::Cedilla,
+        _ => return None,
+    })
+}
+
+fn compose(base: char, diacritic: Diacritic) -> Option<char> {
+    let composed = match (base, diacritic) {
+        ('a', Diacritic::Grave) => 'à',
+        ('a', Diacritic::Acute) => 'á',
+        ('a', Diacritic::Circumflex) => 'â',
+        ('a', Diacritic::Tilde) => 'ã',
+        ('a', Diacritic::Umlaut) => 'ä',
+        ('e', Diacritic::Grave) => 'è',
+        ('e', Diacritic::Acute) => 'é',
+        ('e', Diacritic::Circumflex) => 'ê',
+        ('e', Diacritic::Umlaut) => 'ë',
+        ('i', Diacritic::Grave) => 'ì',
+        ('i', Diacritic::Acute) => 'í',
+        ('i', Diacritic::Circumflex) => 'î',
+        ('i', Diacritic::Umlaut) => 'ï',
+        ('o', Diacritic::Grave) => 'ò',
+        ('o', Diacritic::Acute) => 'ó',
+        ('o', Diacritic::Circumflex) => 'ô',
+        ('o', Diacritic::Tilde) => 'õ',
+        ('o', Diacritic::Umlaut) => 'ö',
+        ('u', Diacritic::Grave) => 'ù',
+        ('u', Diacritic::Acute) => 'ú',
+        ('u', Diacritic::Circumflex) => 'û',
+        ('u', Diacritic::Umlaut) => 'ü',
+        ('n', Diacritic::Tilde) => 'ñ',
+        ('c', Diacritic::Cedilla) => 'ç',
+        _ => return None,
+    };
+    Some(composed)
+}
+
+// Returns true if the keystroke was consumed by the compose sequence and
+// should be swallowed by the hook.
+fn handle_keydown(vk: u32) -> bool {
+    // Shift only selects case or a shifted diacritic; pass it through
+    // without advancing or ending the sequence.
+    if vk == VK_SHIFT as u32 || vk == VK_LSHIFT || vk == VK_RSHIFT {
+        return false;
+    }
+
+    let mut state = STATE.lock().unwrap();
+    match *state {
+        State::Idle => false,
+        State::AwaitingBase => match base_from_vk(vk) {
+            Some(base) => {
+                *state = State::AwaitingDiacritic {
+                    base,
+                    uppercase: shift_held(),
+                };
+                true
+            }
+            None => {
+                drop(state);
+                deactivate();
+                false
+            }
+        },
+        State::AwaitingDiacritic { base, uppercase } => {
+            let shift = shift_held();
+            if let Some(ch) = diacritic_from_vk(vk, shift).and_then(|d| compose(base, d)) {
+                let ch = if uppercase {
+                    ch.to_uppercase().next().unwrap_or(ch)
+                } else {
+                    ch
+                };
+                unsafe { send_unicode_str(&ch.to_string()) };
+            }
+            drop(state);
+            deactivate();
+            true
+        }
+    }
+}
+
+unsafe extern "system" fn hook_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    unsafe {
+        if code >= 0 {
+            let msg = wparam.0 as u32;
+            if msg == WM_KEYDOWN || msg == WM_SYSKEYDOWN {
+                let info = &*(lparam.0 as *const KBDLLHOOKSTRUCT);
+                if handle_keydown(info.vkCode) {
+                    return LRESULT(1);
+                }
+            }
+        }
+        CallNextHookEx(HHOOK(std::ptr::null_mut()), code, wparam, lparam)
+    }
+}
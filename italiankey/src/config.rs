@@ -0,0 +1,220 @@
+// Parses the hotkey remap file that lives next to the executable.
+//
+// Each non-empty, non-comment line has the form:
+//
+//   <accelerator> = "<output>"
+//
+// e.g. `Ctrl+Alt+E = "è"` or `Ctrl+Shift+F13 = "€"`. `#` and `;` start a
+// comment line. Accelerators are tokenized on `+`; every token but the last
+// must be a modifier (`Ctrl`/`Control`, `Alt`, `Shift`, `Win`/`Super`), and
+// the last token is the key.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// MOD_* from WinUser.h
+pub const MOD_ALT: u32 = 0x0001;
+pub const MOD_CONTROL: u32 = 0x0002;
+pub const MOD_SHIFT: u32 = 0x0004;
+pub const MOD_WIN: u32 = 0x0008;
+
+// VIRTUAL-KEY codes not already covered by plain letters/digits.
+const VK_SPACE: u32 = 0x20;
+const VK_TAB: u32 = 0x09;
+const VK_F1: u32 = 0x70;
+const VK_OEM_1: u32 = 0xBA; // ;
+const VK_OEM_PLUS: u32 = 0xBB; // =
+const VK_OEM_COMMA: u32 = 0xBC; // ,
+const VK_OEM_MINUS: u32 = 0xBD; // -
+const VK_OEM_PERIOD: u32 = 0xBE; // .
+const VK_OEM_2: u32 = 0xBF; // /
+const VK_OEM_3: u32 = 0xC0; // `
+const VK_OEM_4: u32 = 0xDB; // [
+const VK_OEM_5: u32 = 0xDC; // \
+const VK_OEM_6: u32 = 0xDD; // ]
+const VK_OEM_7: u32 = 0xDE; // '
+
+/// One parsed `accelerator = "output"` line.
+pub struct HotkeyEntry {
+    pub modifiers: u32,
+    pub vk: u32,
+    pub output: String,
+}
+
+/// A problem found while parsing the config file, with the offending line.
+#[derive(Debug)]
+pub struct ConfigError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "line {}: {}", self.line, self.message)
+        }
+    }
+}
+
+fn err(line: usize, message: impl Into<String>) -> ConfigError {
+    ConfigError {
+        line,
+        message: message.into(),
+    }
+}
+
+// Seeds a fresh config file with the mappings the tool used to hardcode, so
+// out-of-the-box behaviour doesn't change for existing users.
+const DEFAULT_CONFIG: &str = "\
+Ctrl+Alt+E = \"è\"
+Ctrl+Alt+A = \"à\"
+Ctrl+Alt+I = \"ì\"
+Ctrl+Alt+O = \"ò\"
+Ctrl+Alt+U = \"ù\"
+";
+
+/// Path to the config file: `hotkeys.ini` next to the running executable.
+pub fn config_path() -> PathBuf {
+    let dir = std::env::current_exe()
+        .ok()
+        .and_then(|p| p.parent().map(Path::to_path_buf))
+        .unwrap_or_default();
+    dir.join("hotkeys.ini")
+}
+
+/// Loads the config file, creating it with the default mappings if it
+/// doesn't exist yet, then parses every line into a `HotkeyEntry`.
+pub fn load_or_create(path: &Path) -> Result<Vec<HotkeyEntry>, ConfigError> {
+    if !path.exists() {
+        fs::write(path, DEFAULT_CONFIG)
+            .map_err(|e| err(0, format!("failed to create {}: {e}", path.display())))?;
+    }
+
+    let text = fs::read_to_string(path)
+        .map_err(|e| err(0, format!("failed to read {}: {e}", path.display())))?;
+    parse(&text)
+}
+
+fn parse(text: &str) -> Result<Vec<HotkeyEntry>, ConfigError> {
+    let mut entries = Vec::new();
+    let mut seen = HashSet::new();
+
+    for (i, raw_line) in text.lines().enumerate() {
+        let line_no = i + 1;
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        // The RHS is always a quoted string, so split on the '=' immediately
+        // preceding the opening quote rather than the first '=' in the line —
+        // otherwise an accelerator that binds the `=` key itself (e.g.
+        // `Ctrl+Alt+= = "±"`) would be split in the wrong place.
+        let quote_idx = line
+            .find('"')
+            .ok_or_else(|| err(line_no, "expected a quoted output string"))?;
+        let sep_idx = line[..quote_idx]
+            .rfind('=')
+            .ok_or_else(|| err(line_no, "missing '='"))?;
+        let accel = &line[..sep_idx];
+        let value = &line[sep_idx + 1..];
+        let output =
+            parse_quoted(value.trim()).ok_or_else(|| err(line_no, "expected a quoted output string"))?;
+        if output.is_empty() {
+            return Err(err(line_no, "output string must not be empty"));
+        }
+
+        let (modifiers, vk) = parse_accelerator(accel.trim()).map_err(|msg| err(line_no, msg))?;
+        if !seen.insert((modifiers, vk)) {
+            return Err(err(line_no, format!("duplicate accelerator '{}'", accel.trim())));
+        }
+
+        entries.push(HotkeyEntry {
+            modifiers,
+            vk,
+            output,
+        });
+    }
+
+    if entries.is_empty() {
+        return Err(err(0, "config file has no hotkey entries"));
+    }
+
+    Ok(entries)
+}
+
+fn parse_quoted(s: &str) -> Option<String> {
+    let s = s.strip_prefix('"')?;
+    let s = s.strip_suffix('"')?;
+    Some(s.to_string())
+}
+
+fn parse_accelerator(accel: &str) -> Result<(u32, u32), String> {
+    if accel.is_empty() {
+        return Err("empty accelerator".to_string());
+    }
+
+    let tokens: Vec<&str> = accel.split('+').map(str::trim).collect();
+    if tokens.iter().any(|token| token.is_empty()) {
+        return Err("empty token in accelerator".to_string());
+    }
+
+    // Every token but the last must be a modifier; the last token is the key.
+    let (modifier_tokens, key_token) = tokens.split_at(tokens.len() - 1);
+    let key_token = key_token[0];
+
+    let mut modifiers = 0u32;
+    for token in modifier_tokens {
+        match token.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => modifiers |= MOD_CONTROL,
+            "alt" => modifiers |= MOD_ALT,
+            "shift" => modifiers |= MOD_SHIFT,
+            "win" | "super" => modifiers |= MOD_WIN,
+            _ => return Err(format!("unknown modifier '{token}'")),
+        }
+    }
+
+    resolve_key(key_token)
+        .ok_or_else(|| format!("unknown key '{key_token}'"))
+        .map(|vk| (modifiers, vk))
+}
+
+fn resolve_key(token: &str) -> Option<u32> {
+    // Single letters/digits map directly onto their VK code.
+    let mut chars = token.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        let ch = ch.to_ascii_uppercase();
+        if ch.is_ascii_alphanumeric() {
+            return Some(ch as u32);
+        }
+    }
+
+    if let Some(rest) = token.to_ascii_uppercase().strip_prefix('F') {
+        if let Ok(n) = rest.parse::<u32>() {
+            if (1..=24).contains(&n) {
+                return Some(VK_F1 + (n - 1));
+            }
+        }
+    }
+
+    Some(match token {
+        "Space" | "space" | "SPACE" => VK_SPACE,
+        "Tab" | "tab" | "TAB" => VK_TAB,
+        "," => VK_OEM_COMMA,
+        "-" => VK_OEM_MINUS,
+        "." => VK_OEM_PERIOD,
+        "=" => VK_OEM_PLUS,
+        ";" => VK_OEM_1,
+        "/" => VK_OEM_2,
+        "\\" => VK_OEM_5,
+        "'" => VK_OEM_7,
+        "`" => VK_OEM_3,
+        "[" => VK_OEM_4,
+        "]" => VK_OEM_6,
+        _ => return None,
+    })
+}
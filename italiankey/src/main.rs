@@ -1,31 +1,34 @@
 #![windows_subsystem = "windows"]
 
-// This program replicates the AutoHotkey script:
+// This program registers global hotkeys that type arbitrary Unicode strings,
+// driven by a `hotkeys.ini` file next to the executable (see `config.rs`).
+// It also provides a tray icon whose menu has “Enabled” and “Start with
+// Windows” toggles and an “Exit” item.
 //
-//   ^!e::Send('è')
-//   ^!a::Send('à')
-//   ^!i::Send('ì')
-//   ^!o::Send('ò')
-//   ^!u::Send('ù')
-//
-// using Rust and windows-rs. It registers global hotkeys
-// and provides a tray icon with an “Exit” menu item.
-//
-// Keys:
+// By default `hotkeys.ini` is seeded with the mappings this tool used to
+// hardcode:
 //   Ctrl+Alt+E -> è
 //   Ctrl+Alt+A -> à
 //   Ctrl+Alt+I -> ì
 //   Ctrl+Alt+O -> ò
 //   Ctrl+Alt+U -> ù
 
+mod compose;
+mod config;
+mod startup;
+
+use std::collections::HashMap;
 use std::mem::{size_of, zeroed};
 use std::ptr::null;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
 
 use windows::Win32::Foundation::{GetLastError, HINSTANCE, HWND, LPARAM, LRESULT, POINT, WPARAM};
 use windows::Win32::Graphics::Gdi::HBRUSH;
 use windows::Win32::System::LibraryLoader::GetModuleHandleW;
 use windows::Win32::UI::Input::KeyboardAndMouse::{
-    INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYEVENTF_KEYUP, KEYEVENTF_UNICODE, SendInput,
+    HOT_KEY_MODIFIERS, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYEVENTF_KEYUP,
+    KEYEVENTF_UNICODE, SendInput,
 };
 use windows::Win32::UI::Input::KeyboardAndMouse::{RegisterHotKey, UnregisterHotKey, VIRTUAL_KEY};
 use windows::Win32::UI::Shell::{
@@ -35,37 +38,42 @@ use windows::Win32::UI::Shell::{
 use windows::Win32::UI::WindowsAndMessaging::{
     CS_HREDRAW, CS_VREDRAW, CW_USEDEFAULT, CreatePopupMenu, CreateWindowExW, DefWindowProcW,
     DestroyMenu, DispatchMessageW, GWL_WNDPROC, GetCursorPos, GetMessageW, HCURSOR, HICON, HMENU,
-    IDI_APPLICATION, InsertMenuW, LoadIconW, MB_OK, MF_BYPOSITION, MSG, MessageBoxW,
-    PostQuitMessage, RegisterClassW, SetForegroundWindow, SetMenuDefaultItem, SetWindowLongPtrW,
-    TPM_BOTTOMALIGN, TPM_LEFTALIGN, TPM_RETURNCMD, TPM_RIGHTBUTTON, TrackPopupMenuEx,
-    TranslateMessage, UnregisterClassW, WM_COMMAND, WM_DESTROY, WM_HOTKEY, WM_RBUTTONUP, WM_USER,
-    WNDCLASSW, WS_EX_TOOLWINDOW, WS_OVERLAPPEDWINDOW, WS_VISIBLE,
+    IDI_APPLICATION, InsertMenuW, LoadIconW, MB_OK, MF_BYPOSITION, MF_CHECKED, MF_UNCHECKED, MSG,
+    MessageBoxW, PostQuitMessage, RegisterClassW, RegisterWindowMessageW, SetForegroundWindow,
+    SetMenuDefaultItem, SetWindowLongPtrW, TPM_BOTTOMALIGN, TPM_LEFTALIGN, TPM_RETURNCMD,
+    TPM_RIGHTBUTTON, TrackPopupMenuEx, TranslateMessage, UnregisterClassW, WM_COMMAND,
+    WM_DESTROY, WM_HOTKEY, WM_RBUTTONUP, WM_USER, WNDCLASSW, WS_EX_TOOLWINDOW,
+    WS_OVERLAPPEDWINDOW, WS_VISIBLE,
 };
 use windows::core::PCWSTR;
 
-// Hotkey IDs
-const HOTKEY_ID_E: i32 = 1;
-const HOTKEY_ID_A: i32 = 2;
-const HOTKEY_ID_I: i32 = 3;
-const HOTKEY_ID_O: i32 = 4;
-const HOTKEY_ID_U: i32 = 5;
-
 // Tray menu command IDs
 const ID_TRAY_EXIT: u16 = 1000;
+const ID_TRAY_ENABLED: u16 = 1001;
+const ID_TRAY_STARTUP: u16 = 1002;
 
 // Custom tray callback message
 const WM_TRAYICON: u32 = WM_USER + 1;
 
-// MOD_* from WinUser.h
-const MOD_ALT: u32 = 0x0001;
-const MOD_CONTROL: u32 = 0x0002;
+// Hotkey ID for the compose-mode activation combo (Ctrl+Alt+Space), kept
+// outside the 1..=N range generated from hotkeys.ini.
+const COMPOSE_ACTIVATE_ID: i32 = i32::MAX;
+const VK_SPACE: u32 = 0x20;
+
+// Maps generated hotkey ID -> the string it should type, populated at
+// startup from `hotkeys.ini` and consulted from `WM_HOTKEY`.
+static HOTKEYS: OnceLock<HashMap<i32, String>> = OnceLock::new();
+
+// (id, modifiers, vk) for every hotkeys.ini entry, kept around so the
+// "Enabled" tray toggle can unregister and later re-register them.
+static HOTKEY_DEFS: OnceLock<Vec<(i32, u32, u32)>> = OnceLock::new();
+
+// Whether remapping is currently active (toggled from the tray menu).
+static ENABLED: AtomicBool = AtomicBool::new(true);
 
-// VIRTUAL-KEY codes for letters
-const VK_A: u32 = 0x41;
-const VK_E: u32 = 0x45;
-const VK_I: u32 = 0x49;
-const VK_O: u32 = 0x4F;
-const VK_U: u32 = 0x55;
+// The dynamic "TaskbarCreated" message ID, broadcast by Explorer whenever it
+// (re)starts. Can't be a match arm constant since it's assigned at runtime.
+static TASKBAR_CREATED: OnceLock<u32> = OnceLock::new();
 
 // Simple helper: convert &str -> wide null-terminated UTF-16
 fn to_wide(s: &str) -> Vec<u16> {
@@ -120,6 +128,62 @@ unsafe fn send_unicode_char(ch: char) {
     }
 }
 
+// Send a sequence of Unicode characters, e.g. the output string configured
+// for a hotkey.
+pub(crate) unsafe fn send_unicode_str(s: &str) {
+    unsafe {
+        for ch in s.chars() {
+            send_unicode_char(ch);
+        }
+    }
+}
+
+// Registers or unregisters every remap hotkey and the compose-mode
+// activation combo in one go, backing the tray menu's "Enabled" toggle.
+unsafe fn set_hotkeys_enabled(hwnd: HWND, enabled: bool) {
+    unsafe {
+        let defs = HOTKEY_DEFS.get().cloned().unwrap_or_default();
+        if enabled {
+            for (id, modifiers, vk) in defs {
+                let _ = RegisterHotKey(hwnd, id, HOT_KEY_MODIFIERS(modifiers), vk);
+            }
+            let _ = RegisterHotKey(
+                hwnd,
+                COMPOSE_ACTIVATE_ID,
+                HOT_KEY_MODIFIERS(config::MOD_CONTROL | config::MOD_ALT),
+                VK_SPACE,
+            );
+        } else {
+            for (id, _, _) in defs {
+                let _ = UnregisterHotKey(hwnd, id);
+            }
+            let _ = UnregisterHotKey(hwnd, COMPOSE_ACTIVATE_ID);
+        }
+        ENABLED.store(enabled, Ordering::SeqCst);
+    }
+}
+
+// Handles a tray menu command shared by the WM_TRAYICON (TrackPopupMenuEx
+// return value) and WM_COMMAND paths. Returns true if the app should quit.
+unsafe fn handle_tray_command(hwnd: HWND, cmd: u16) -> bool {
+    unsafe {
+        match cmd {
+            ID_TRAY_EXIT => true,
+            ID_TRAY_ENABLED => {
+                set_hotkeys_enabled(hwnd, !ENABLED.load(Ordering::SeqCst));
+                false
+            }
+            ID_TRAY_STARTUP => {
+                if let Err(e) = startup::set_enabled(!startup::is_enabled()) {
+                    fatal(&format!("Failed to update startup registry entry: {e}"));
+                }
+                false
+            }
+            _ => false,
+        }
+    }
+}
+
 // Create and show tray context menu. Returns command ID (e.g. ID_TRAY_EXIT) or 0.
 unsafe fn show_tray_menu(hwnd: HWND) -> u16 {
     unsafe {
@@ -128,7 +192,35 @@ unsafe fn show_tray_menu(hwnd: HWND) -> u16 {
             Err(_) => fatal("CreatePopupMenu failed"),
         };
 
-        // Insert "Exit" at position 0 (MF_BYPOSITION)
+        let enabled_check = if ENABLED.load(Ordering::SeqCst) {
+            MF_CHECKED
+        } else {
+            MF_UNCHECKED
+        };
+        let enabled_text = to_wide("Enabled");
+        let _ = InsertMenuW(
+            h_menu,
+            u32::MAX,
+            MF_BYPOSITION | enabled_check,
+            ID_TRAY_ENABLED as usize,
+            PCWSTR(enabled_text.as_ptr()),
+        );
+
+        let startup_check = if startup::is_enabled() {
+            MF_CHECKED
+        } else {
+            MF_UNCHECKED
+        };
+        let startup_text = to_wide("Start with Windows");
+        let _ = InsertMenuW(
+            h_menu,
+            u32::MAX,
+            MF_BYPOSITION | startup_check,
+            ID_TRAY_STARTUP as usize,
+            PCWSTR(startup_text.as_ptr()),
+        );
+
+        // Insert "Exit" at the bottom (MF_BYPOSITION)
         let text = to_wide("Exit");
         let _ = InsertMenuW(
             h_menu,
@@ -173,7 +265,7 @@ unsafe fn add_tray_icon(hwnd: HWND, hinstance: HINSTANCE) {
         nid.hIcon = h_icon;
 
         // Tooltip
-        let tip = to_wide("Italian accents hotkey");
+        let tip = to_wide("Hotkey remap");
         // NOTIFYICONDATAW::szTip is [u16; 128]
         let max = nid.szTip.len().min(tip.len());
         nid.szTip[..max].copy_from_slice(&tip[..max]);
@@ -217,22 +309,30 @@ unsafe fn remove_tray_icon(hwnd: HWND) {
 // Window procedure
 extern "system" fn wnd_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
     unsafe {
+        if Some(&msg) == TASKBAR_CREATED.get() {
+            // Explorer just (re)started and wiped out its notification area;
+            // re-add our icon so it doesn't vanish until the app is relaunched.
+            if let Ok(hmodule) = GetModuleHandleW(PCWSTR(null())) {
+                add_tray_icon(hwnd, HINSTANCE(hmodule.0));
+                update_tray_tooltip(hwnd, "lu2000luk's hotkey remap");
+            }
+            return LRESULT(0);
+        }
+
         match msg {
             WM_HOTKEY => {
-                match wparam.0 as i32 {
-                    HOTKEY_ID_E => send_unicode_char('è'),
-                    HOTKEY_ID_A => send_unicode_char('à'),
-                    HOTKEY_ID_I => send_unicode_char('ì'),
-                    HOTKEY_ID_O => send_unicode_char('ò'),
-                    HOTKEY_ID_U => send_unicode_char('ù'),
-                    _ => {}
+                let id = wparam.0 as i32;
+                if id == COMPOSE_ACTIVATE_ID {
+                    compose::activate();
+                } else if let Some(output) = HOTKEYS.get().and_then(|map| map.get(&id)) {
+                    send_unicode_str(output);
                 }
                 LRESULT(0)
             }
             WM_TRAYICON => {
                 if lparam.0 as u32 == WM_RBUTTONUP {
                     let cmd = show_tray_menu(hwnd);
-                    if cmd == ID_TRAY_EXIT {
+                    if cmd != 0 && handle_tray_command(hwnd, cmd) {
                         PostQuitMessage(0);
                     }
                 }
@@ -240,8 +340,10 @@ extern "system" fn wnd_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM
             }
             WM_COMMAND => {
                 let cmd_id = (wparam.0 & 0xFFFF) as u16;
-                if cmd_id == ID_TRAY_EXIT {
-                    PostQuitMessage(0);
+                if matches!(cmd_id, ID_TRAY_EXIT | ID_TRAY_ENABLED | ID_TRAY_STARTUP) {
+                    if handle_tray_command(hwnd, cmd_id) {
+                        PostQuitMessage(0);
+                    }
                     return LRESULT(0);
                 }
                 DefWindowProcW(hwnd, msg, wparam, lparam)
@@ -283,6 +385,15 @@ fn main() {
             fatal("RegisterClassW failed");
         }
 
+        // Needed so we can re-add the tray icon if Explorer restarts. Only
+        // store a non-zero id: 0 would mean RegisterWindowMessageW failed,
+        // and WM_NULL (msg 0) must never be mistaken for TaskbarCreated.
+        let taskbar_created_name = to_wide("TaskbarCreated");
+        let taskbar_created = RegisterWindowMessageW(PCWSTR(taskbar_created_name.as_ptr()));
+        if taskbar_created != 0 {
+            let _ = TASKBAR_CREATED.set(taskbar_created);
+        }
+
         // Create a hidden tool window (no taskbar button)
         let window_name = to_wide("HotkeyTrayWindow");
         let hwnd = match CreateWindowExW(
@@ -306,30 +417,44 @@ fn main() {
         // Ensure our wnd_proc is set
         SetWindowLongPtrW(hwnd, GWL_WNDPROC, wnd_proc as isize);
 
-        // Register hotkeys
-        let mods = MOD_CONTROL | MOD_ALT;
-
-        use windows::Win32::UI::Input::KeyboardAndMouse::HOT_KEY_MODIFIERS;
+        // Load the remap config, creating it with the default mappings the
+        // first time the tool runs.
+        let config_path = config::config_path();
+        let entries = match config::load_or_create(&config_path) {
+            Ok(entries) => entries,
+            Err(e) => fatal(&format!("Invalid {}: {e}", config_path.display())),
+        };
 
-        if RegisterHotKey(hwnd, HOTKEY_ID_E, HOT_KEY_MODIFIERS(mods), VK_E).is_err() {
-            fatal("RegisterHotKey Ctrl+Alt+E failed");
-        }
-        if RegisterHotKey(hwnd, HOTKEY_ID_A, HOT_KEY_MODIFIERS(mods), VK_A).is_err() {
-            fatal("RegisterHotKey Ctrl+Alt+A failed");
-        }
-        if RegisterHotKey(hwnd, HOTKEY_ID_I, HOT_KEY_MODIFIERS(mods), VK_I).is_err() {
-            fatal("RegisterHotKey Ctrl+Alt+I failed");
-        }
-        if RegisterHotKey(hwnd, HOTKEY_ID_O, HOT_KEY_MODIFIERS(mods), VK_O).is_err() {
-            fatal("RegisterHotKey Ctrl+Alt+O failed");
+        // Register one hotkey per config entry and remember what it types.
+        let mut hotkeys = HashMap::with_capacity(entries.len());
+        let mut hotkey_defs = Vec::with_capacity(entries.len());
+        for (i, entry) in entries.into_iter().enumerate() {
+            let id = i as i32 + 1;
+            if RegisterHotKey(hwnd, id, HOT_KEY_MODIFIERS(entry.modifiers), entry.vk).is_err() {
+                fatal(&format!("RegisterHotKey failed for entry {} in hotkeys.ini", i + 1));
+            }
+            hotkey_defs.push((id, entry.modifiers, entry.vk));
+            hotkeys.insert(id, entry.output);
         }
-        if RegisterHotKey(hwnd, HOTKEY_ID_U, HOT_KEY_MODIFIERS(mods), VK_U).is_err() {
-            fatal("RegisterHotKey Ctrl+Alt+U failed");
+        let _ = HOTKEYS.set(hotkeys);
+        let _ = HOTKEY_DEFS.set(hotkey_defs);
+
+        // Compose-key mode: Ctrl+Alt+Space arms the keyboard hook for the
+        // next base-letter + diacritic keystroke pair.
+        if RegisterHotKey(
+            hwnd,
+            COMPOSE_ACTIVATE_ID,
+            HOT_KEY_MODIFIERS(config::MOD_CONTROL | config::MOD_ALT),
+            VK_SPACE,
+        )
+        .is_err()
+        {
+            fatal("RegisterHotKey Ctrl+Alt+Space (compose mode) failed");
         }
 
         // Add tray icon and tooltip
         add_tray_icon(hwnd, hinstance);
-        update_tray_tooltip(hwnd, "lu2000luk's italian remap");
+        update_tray_tooltip(hwnd, "lu2000luk's hotkey remap");
 
         // Message loop
         let mut msg: MSG = zeroed();
@@ -346,11 +471,12 @@ fn main() {
         }
 
         // Cleanup
-        UnregisterHotKey(hwnd, HOTKEY_ID_E);
-        UnregisterHotKey(hwnd, HOTKEY_ID_A);
-        UnregisterHotKey(hwnd, HOTKEY_ID_I);
-        UnregisterHotKey(hwnd, HOTKEY_ID_O);
-        UnregisterHotKey(hwnd, HOTKEY_ID_U);
+        if let Some(map) = HOTKEYS.get() {
+            for &id in map.keys() {
+                let _ = UnregisterHotKey(hwnd, id);
+            }
+        }
+        let _ = UnregisterHotKey(hwnd, COMPOSE_ACTIVATE_ID);
 
         let _ = UnregisterClassW(PCWSTR(class_name.as_ptr()), hinstance);
     }
@@ -0,0 +1,70 @@
+// Manages the optional "run at Windows startup" entry under
+// HKCU\Software\Microsoft\Windows\CurrentVersion\Run, used by the tray
+// menu's "Start with Windows" toggle.
+
+use windows::Win32::Foundation::ERROR_SUCCESS;
+use windows::Win32::System::Registry::{
+    HKEY, HKEY_CURRENT_USER, KEY_READ, KEY_WRITE, REG_SZ, RegCloseKey, RegDeleteValueW,
+    RegOpenKeyExW, RegQueryValueExW, RegSetValueExW,
+};
+use windows::core::PCWSTR;
+
+const RUN_KEY: &str = "Software\\Microsoft\\Windows\\CurrentVersion\\Run";
+const VALUE_NAME: &str = "ItalianKeyHotkeyRemap";
+
+fn to_wide(s: &str) -> Vec<u16> {
+    s.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+/// Whether the app currently has a "run at startup" registry entry.
+pub fn is_enabled() -> bool {
+    let subkey = to_wide(RUN_KEY);
+    let mut hkey = HKEY::default();
+    let opened =
+        unsafe { RegOpenKeyExW(HKEY_CURRENT_USER, PCWSTR(subkey.as_ptr()), Some(0), KEY_READ, &mut hkey) };
+    if opened != ERROR_SUCCESS {
+        return false;
+    }
+
+    let value_name = to_wide(VALUE_NAME);
+    let queried = unsafe { RegQueryValueExW(hkey, PCWSTR(value_name.as_ptr()), None, None, None, None) };
+    unsafe {
+        let _ = RegCloseKey(hkey);
+    }
+    queried == ERROR_SUCCESS
+}
+
+/// Enables or disables "run at startup" by writing or removing the registry
+/// value, pointed at the currently running executable.
+pub fn set_enabled(enabled: bool) -> Result<(), String> {
+    let exe_path = std::env::current_exe().map_err(|e| format!("failed to locate executable: {e}"))?;
+
+    let subkey = to_wide(RUN_KEY);
+    let mut hkey = HKEY::default();
+    let opened = unsafe {
+        RegOpenKeyExW(HKEY_CURRENT_USER, PCWSTR(subkey.as_ptr()), Some(0), KEY_WRITE, &mut hkey)
+    };
+    if opened != ERROR_SUCCESS {
+        return Err(format!("failed to open {RUN_KEY}"));
+    }
+
+    let value_name = to_wide(VALUE_NAME);
+    let status = if enabled {
+        let path = to_wide(&exe_path.to_string_lossy());
+        let bytes: &[u8] =
+            unsafe { std::slice::from_raw_parts(path.as_ptr() as *const u8, path.len() * 2) };
+        unsafe { RegSetValueExW(hkey, PCWSTR(value_name.as_ptr()), 0, REG_SZ, Some(bytes)) }
+    } else {
+        unsafe { RegDeleteValueW(hkey, PCWSTR(value_name.as_ptr())) }
+    };
+
+    unsafe {
+        let _ = RegCloseKey(hkey);
+    }
+
+    if status == ERROR_SUCCESS {
+        Ok(())
+    } else {
+        Err(format!("registry update failed with error {}", status.0))
+    }
+}
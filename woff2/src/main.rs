@@ -4,9 +4,10 @@ use std::env;
 use std::ffi::OsStr;
 use std::fs;
 use std::io::Cursor;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use woff2_patched::decode::{convert_woff2_to_ttf, is_woff2};
+use woff2_patched::encode::convert_ttf_to_woff2;
 
 #[cfg(target_os = "windows")]
 use std::os::windows::ffi::OsStrExt;
@@ -15,55 +16,133 @@ use windows::core::PCWSTR;
 #[cfg(target_os = "windows")]
 use windows::Win32::Foundation::HWND;
 #[cfg(target_os = "windows")]
+use windows::Win32::System::Com::{
+    CoCreateInstance, CoInitializeEx, CoTaskMemFree, CoUninitialize, CLSCTX_INPROC_SERVER,
+    COINIT_APARTMENTTHREADED,
+};
+#[cfg(target_os = "windows")]
+use windows::Win32::UI::Shell::{
+    FileOpenDialog, IFileOpenDialog, IShellItem, COMDLG_FILTERSPEC, FOS_ALLOWMULTISELECT,
+    SIGDN_FILESYSPATH,
+};
+#[cfg(target_os = "windows")]
 use windows::Win32::UI::WindowsAndMessaging::{MessageBoxW, MB_ICONERROR, MB_OK, MESSAGEBOX_STYLE};
 
 fn main() {
-    // When a file is dragged onto the EXE or opened via "Open with", the path appears as the first arg.
-    let mut args = env::args_os();
-    let _exe = args.next(); // skip program name
-    let Some(input_os) = args.next() else {
-        // No file provided: exit silently (common for drag-and-drop utilities when double-clicked).
-        return;
-    };
-
-    let input_path = PathBuf::from(input_os);
-
-    // Read file
-    let buffer = match fs::read(&input_path) {
-        Ok(b) => b,
-        Err(_) => {
-            message_box("Failed to read the file.", "woff2decomp");
+    // When files are dragged onto the EXE or opened via "Open with", their
+    // paths appear as args; double-clicking with no args falls back to a
+    // native file picker so the tool is usable standalone.
+    let mut paths: Vec<PathBuf> = env::args_os().skip(1).map(PathBuf::from).collect();
+
+    if paths.is_empty() {
+        paths = pick_files();
+        if paths.is_empty() {
             return;
         }
-    };
+    }
 
-    // Validate content is WOFF2
-    if !is_woff2(&buffer) {
-        message_box("Invalid file type", "woff2decomp");
-        return;
+    let mut summary = String::new();
+    for path in &paths {
+        match convert_one(path) {
+            Ok(output) => {
+                summary.push_str(&format!("{} -> {}\n", path.display(), output.display()));
+            }
+            Err(e) => {
+                summary.push_str(&format!("{}: FAILED ({e})\n", path.display()));
+            }
+        }
     }
 
-    // Convert WOFF2 -> TTF
-    let mut cursor = Cursor::new(buffer);
-    let ttf_bytes = match convert_woff2_to_ttf(&mut cursor) {
-        Ok(ttf) => ttf,
-        Err(_) => {
-            message_box("Conversion failed.", "woff2decomp");
-            return;
+    message_box(summary.trim_end(), "woff2decomp");
+}
+
+// Converts a single file, auto-detecting direction from its contents:
+// WOFF2 -> TTF, or TTF/OTF -> WOFF2.
+fn convert_one(input_path: &Path) -> Result<PathBuf, String> {
+    let buffer = fs::read(input_path).map_err(|e| format!("failed to read file: {e}"))?;
+
+    if is_woff2(&buffer) {
+        let mut cursor = Cursor::new(buffer);
+        let ttf_bytes =
+            convert_woff2_to_ttf(&mut cursor).map_err(|e| format!("decode failed: {e}"))?;
+
+        let mut output_path = input_path.to_path_buf();
+        output_path.set_extension("ttf");
+        fs::write(&output_path, ttf_bytes).map_err(|e| format!("failed to write output: {e}"))?;
+        Ok(output_path)
+    } else if is_sfnt(&buffer) {
+        let woff2_bytes = convert_ttf_to_woff2(&buffer).map_err(|e| format!("encode failed: {e}"))?;
+
+        let mut output_path = input_path.to_path_buf();
+        output_path.set_extension("woff2");
+        fs::write(&output_path, woff2_bytes).map_err(|e| format!("failed to write output: {e}"))?;
+        Ok(output_path)
+    } else {
+        Err("not a WOFF2, TTF, or OTF file".to_string())
+    }
+}
+
+// Sniffs the sfnt magic shared by TTF/OTF: 0x00010000, "OTTO", "true", or "typ1".
+fn is_sfnt(buffer: &[u8]) -> bool {
+    matches!(
+        buffer.get(0..4),
+        Some([0x00, 0x01, 0x00, 0x00]) | Some(b"OTTO") | Some(b"true") | Some(b"typ1")
+    )
+}
+
+#[cfg(target_os = "windows")]
+fn pick_files() -> Vec<PathBuf> {
+    unsafe {
+        if CoInitializeEx(None, COINIT_APARTMENTTHREADED).is_err() {
+            return Vec::new();
         }
-    };
 
-    // Build output path (same filename, .ttf extension)
-    let mut output_path = input_path.clone();
-    output_path.set_extension("ttf");
+        let paths = pick_files_inner().unwrap_or_default();
+
+        CoUninitialize();
+        paths
+    }
+}
+
+#[cfg(target_os = "windows")]
+unsafe fn pick_files_inner() -> windows::core::Result<Vec<PathBuf>> {
+    unsafe {
+        let dialog: IFileOpenDialog =
+            CoCreateInstance(&FileOpenDialog, None, CLSCTX_INPROC_SERVER)?;
+
+        let mut options = dialog.GetOptions()?;
+        options |= FOS_ALLOWMULTISELECT;
+        dialog.SetOptions(options)?;
+
+        let filter_name = to_wide("WOFF2/TrueType/OpenType fonts");
+        let filter_spec = to_wide("*.woff2;*.ttf;*.otf");
+        let filters = [COMDLG_FILTERSPEC {
+            pszName: PCWSTR(filter_name.as_ptr()),
+            pszSpec: PCWSTR(filter_spec.as_ptr()),
+        }];
+        dialog.SetFileTypes(&filters)?;
+
+        dialog.Show(HWND::default())?;
+
+        let results = dialog.GetResults()?;
+        let count = results.GetCount()?;
 
-    // Write TTF
-    if let Err(_) = fs::write(&output_path, ttf_bytes) {
-        message_box("Failed to write the .ttf file.", "woff2decomp");
-        return;
+        let mut paths = Vec::with_capacity(count as usize);
+        for i in 0..count {
+            let item: IShellItem = results.GetItemAt(i)?;
+            let pwstr = item.GetDisplayName(SIGDN_FILESYSPATH)?;
+            let path = PathBuf::from(pwstr.to_string().unwrap_or_default());
+            CoTaskMemFree(Some(pwstr.0 as *const _));
+            paths.push(path);
+        }
+
+        Ok(paths)
     }
+}
 
-    // Success: no message shown to keep drag-and-drop workflow clean.
+#[cfg(not(target_os = "windows"))]
+fn pick_files() -> Vec<PathBuf> {
+    Vec::new()
 }
 
 fn message_box(text: &str, caption: &str) {